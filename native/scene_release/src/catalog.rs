@@ -0,0 +1,55 @@
+//! Known vocabulary used to classify the dot/space-delimited tokens in a release name.
+
+pub const RESOLUTIONS: &[&str] = &["2160p", "1080p", "720p", "576p", "480p", "4k", "8k"];
+
+pub const SOURCES: &[&str] = &[
+    "bluray", "bdremux", "remux", "bdrip", "bd", "web-dl", "webdl", "webrip", "web", "hdtv",
+    "pdtv", "dvdrip", "dvd", "hdrip", "cam", "ts",
+];
+
+pub const FORMATS: &[&str] = &["x264", "x265", "h264", "h265", "hevc", "avc", "xvid", "divx"];
+
+pub const AUDIO: &[&str] = &[
+    "truehd", "atmos", "dts-hd", "dts", "ddp5.1", "ddp2.0", "dd5.1", "ac3", "eac3", "aac", "flac",
+    "mp3",
+];
+
+pub const FLAGS: &[&str] = &[
+    "proper", "repack", "internal", "limited", "extended", "unrated", "remastered",
+];
+
+pub const HDR: &[&str] = &["hdr10+", "hdr10", "hdr", "dv", "dolby.vision"];
+
+/// Lowercase an owned token once so every classifier below can do a cheap `==` / `contains`.
+fn norm(token: &str) -> String {
+    token.to_ascii_lowercase()
+}
+
+pub fn is_resolution(token: &str) -> bool {
+    RESOLUTIONS.contains(&norm(token).as_str())
+}
+
+pub fn is_source(token: &str) -> bool {
+    SOURCES.contains(&norm(token).as_str())
+}
+
+pub fn is_format(token: &str) -> bool {
+    FORMATS.contains(&norm(token).as_str())
+}
+
+pub fn is_audio(token: &str) -> bool {
+    AUDIO.contains(&norm(token).as_str())
+}
+
+pub fn is_flag(token: &str) -> bool {
+    FLAGS.contains(&norm(token).as_str())
+}
+
+pub fn is_hdr(token: &str) -> bool {
+    HDR.contains(&norm(token).as_str())
+}
+
+/// True when the token is any of the known metadata categories above.
+pub fn is_known_metadata_token(token: &str) -> bool {
+    is_resolution(token) || is_source(token) || is_format(token) || is_audio(token) || is_flag(token) || is_hdr(token)
+}
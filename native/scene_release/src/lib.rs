@@ -0,0 +1,4 @@
+mod catalog;
+mod library_path;
+pub mod parser;
+pub mod types;
@@ -0,0 +1,71 @@
+//! Renders a [`ParsedRelease`] into the Plex/Jellyfin-style library layout so callers can move a
+//! matched file straight into place instead of hand-assembling the path themselves.
+
+use crate::types::ParsedRelease;
+
+const UNSAFE_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !UNSAFE_CHARS.contains(c))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn folder_name(title: &str, year: Option<u32>) -> String {
+    match year {
+        Some(year) => format!("{title} ({year})"),
+        None => title.to_string(),
+    }
+}
+
+fn episode_code(season: u32, episode: u32, episodes: &[u32]) -> String {
+    match episodes.last() {
+        Some(&last) if episodes.len() > 1 => format!("S{season:02}E{episode:02}-E{last:02}"),
+        _ => format!("S{season:02}E{episode:02}"),
+    }
+}
+
+fn render_tv(parsed: &ParsedRelease, extension: &str) -> String {
+    let title = sanitize(&parsed.title);
+    let show_folder = folder_name(&title, parsed.year);
+    let season = parsed.season.unwrap_or_default();
+    let season_folder = format!("Season {season:02}");
+    let episode = parsed.episode.unwrap_or_default();
+
+    let mut file_name = format!("{title} - {}", episode_code(season, episode, &parsed.episodes));
+    if let Some(episode_title) = &parsed.episode_title {
+        file_name.push_str(" - ");
+        file_name.push_str(&sanitize(episode_title));
+    }
+
+    format!("{show_folder}/{season_folder}/{file_name}.{extension}")
+}
+
+fn render_movie(parsed: &ParsedRelease, extension: &str) -> String {
+    let title = sanitize(&parsed.title);
+    let folder = folder_name(&title, parsed.year);
+
+    let mut file_name = folder.clone();
+    if let Some(edition) = &parsed.edition {
+        file_name.push_str(&format!(" [{}]", sanitize(edition)));
+    }
+    if let Some(resolution) = &parsed.resolution {
+        file_name.push_str(&format!(" [{resolution}]"));
+    }
+
+    format!("{folder}/{file_name}.{extension}")
+}
+
+/// Renders `parsed` into its canonical library path. TV releases (anything with a `season`) use
+/// the show/season/episode layout; everything else renders as a movie.
+pub fn render(parsed: &ParsedRelease, extension: &str) -> String {
+    if parsed.season.is_some() {
+        render_tv(parsed, extension)
+    } else {
+        render_movie(parsed, extension)
+    }
+}
@@ -0,0 +1,620 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use std::collections::HashSet;
+
+use crate::catalog;
+use crate::library_path;
+use crate::types::{ParseReport, ParsedRelease, SearchQuery, UnknownToken};
+
+fn year_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?:19|20)\d{2}").unwrap())
+}
+
+fn season_episode_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)S(\d{1,2})E(\d{1,2})(?:-E?(\d{1,2}))?").unwrap())
+}
+
+fn group_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"-([A-Za-z0-9]+)$").unwrap())
+}
+
+fn anime_group_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\[([^\]]+)\]\s*").unwrap())
+}
+
+fn anime_crc32_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\[([0-9A-Fa-f]{8})\]\s*$").unwrap())
+}
+
+fn anime_episode_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(\d{1,4})(?:-(\d{1,4}))?(?:[Vv](\d))?$").unwrap())
+}
+
+/// Parses scene/p2p release names into a [`ParsedRelease`].
+///
+/// A parser is constructed for a given `release_type` ("movie", "series", "tv", "anime") because
+/// a handful of conventions (episode numbering, directory layout) differ by type; the token
+/// classification rules in [`catalog`] are shared across all of them.
+pub struct ReleaseParser {
+    release_type: String,
+}
+
+// `ReleaseParser` holds only an owned `String` with no interior mutability, so a shared
+// `&ReleaseParser` is safe to hand to every worker thread in `nif_parse_batch`'s rayon `par_iter`.
+const _: fn() = || {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<ReleaseParser>();
+};
+
+impl ReleaseParser {
+    pub fn new(release_type: &str) -> Self {
+        ReleaseParser {
+            release_type: release_type.to_string(),
+        }
+    }
+
+    pub fn parse(&self, release_name: &str) -> ParsedRelease {
+        if self.release_type == "anime" {
+            self.parse_anime(release_name)
+        } else {
+            self.parse_standard(release_name)
+        }
+    }
+
+    fn base_release(&self, release_name: &str) -> ParsedRelease {
+        ParsedRelease {
+            release: release_name.to_string(),
+            release_type: self.release_type.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn parse_standard(&self, release_name: &str) -> ParsedRelease {
+        let mut parsed = self.base_release(release_name);
+        let without_extension = strip_extension(release_name);
+
+        let group = group_re()
+            .captures(&without_extension)
+            .map(|caps| caps[1].to_string());
+        let without_group = match &group {
+            Some(_) => group_re().replace(&without_extension, "").to_string(),
+            None => without_extension.clone(),
+        };
+        parsed.group = group;
+
+        let mut season_episode_end = without_group.len();
+        if let Some(caps) = season_episode_re().captures(&without_group) {
+            parsed.season = caps.get(1).and_then(|m| m.as_str().parse().ok());
+            parsed.episode = caps.get(2).and_then(|m| m.as_str().parse().ok());
+            if let Some(last_episode) = caps.get(3).and_then(|m| m.as_str().parse::<u32>().ok()) {
+                if let (Some(first), Some(last)) = (parsed.episode, Some(last_episode)) {
+                    parsed.episodes = (first..=last).collect();
+                }
+            }
+            let whole = caps.get(0).unwrap();
+            season_episode_end = whole.start();
+        }
+
+        let mut title_end = season_episode_end;
+
+        if let Some(m) = year_re().find(&without_group) {
+            if m.start() < title_end {
+                parsed.year = m.as_str().parse().ok();
+                title_end = title_end.min(m.start());
+            }
+        }
+
+        for (position, token) in tokenize_with_positions(&without_group) {
+            let is_catalog_token = if catalog::is_resolution(&token) && parsed.resolution.is_none() {
+                parsed.resolution = Some(token.clone());
+                true
+            } else if catalog::is_source(&token) && parsed.source.is_none() {
+                parsed.source = Some(token.clone());
+                true
+            } else if catalog::is_format(&token) && parsed.format.is_none() {
+                parsed.format = Some(token.clone());
+                true
+            } else if catalog::is_audio(&token) && parsed.audio.is_none() {
+                parsed.audio = Some(token.clone());
+                true
+            } else if catalog::is_hdr(&token) {
+                parsed.hdr.push(token.clone());
+                true
+            } else if catalog::is_flag(&token) {
+                parsed.flags.push(token.clone());
+                true
+            } else {
+                false
+            };
+
+            // Any catalog token, wherever it sits, marks noise that shouldn't leak into the
+            // title — not just the first one found after the year/season-episode anchor.
+            if is_catalog_token {
+                title_end = title_end.min(position);
+            }
+        }
+
+        parsed.title = clean_title(&without_group[..title_end.min(without_group.len())]);
+        parsed.search_query = build_search_query(&parsed);
+        parsed
+    }
+
+    fn parse_anime(&self, release_name: &str) -> ParsedRelease {
+        let mut parsed = self.base_release(release_name);
+        let without_extension = strip_extension(release_name);
+
+        let mut remainder = without_extension.as_str();
+
+        if let Some(caps) = anime_group_re().captures(remainder) {
+            parsed.group = Some(caps[1].to_string());
+            let matched = caps.get(0).unwrap().as_str();
+            remainder = &remainder[matched.len()..];
+        }
+
+        let without_extension_owned;
+        if let Some(caps) = anime_crc32_re().captures(remainder) {
+            parsed.crc32 = Some(caps[1].to_uppercase());
+            let whole = caps.get(0).unwrap();
+            without_extension_owned = remainder[..whole.start()].trim_end().to_string();
+            remainder = &without_extension_owned;
+        }
+
+        // Parenthesized groups (e.g. "(BD 1080p)") carry source/resolution/audio hints.
+        let paren_re = {
+            static RE: OnceLock<Regex> = OnceLock::new();
+            RE.get_or_init(|| Regex::new(r"\(([^)]*)\)").unwrap())
+        };
+        for caps in paren_re.captures_iter(remainder) {
+            for token in caps[1].split_whitespace() {
+                if catalog::is_resolution(token) && parsed.resolution.is_none() {
+                    parsed.resolution = Some(token.to_string());
+                } else if catalog::is_source(token) && parsed.source.is_none() {
+                    parsed.source = Some(token.to_string());
+                } else if catalog::is_audio(token) && parsed.audio.is_none() {
+                    parsed.audio = Some(token.to_string());
+                }
+            }
+        }
+        let without_parens = paren_re.replace_all(remainder, "").trim().to_string();
+
+        // What's left is "Title - <episode>[v2]" or "Title - <start>-<end>[v2]".
+        if let Some((title_part, episode_part)) = without_parens.rsplit_once(" - ") {
+            parsed.title = clean_title(title_part);
+
+            if let Some(caps) = anime_episode_re().captures(episode_part.trim()) {
+                let first: u32 = caps[1].parse().unwrap_or_default();
+                parsed.absolute_episode = Some(first);
+
+                if let Some(last) = caps.get(2).and_then(|m| m.as_str().parse::<u32>().ok()) {
+                    parsed.episodes = (first..=last).collect();
+                }
+
+                if let Some(version) = caps.get(3) {
+                    parsed.version = Some(format!("v{}", version.as_str()));
+                }
+            }
+        } else {
+            parsed.title = clean_title(&without_parens);
+        }
+
+        parsed.search_query = build_search_query(&parsed);
+        parsed
+    }
+
+    pub fn parse_series_directory(&self, directory_name: &str) -> ParsedRelease {
+        ReleaseParser::new("series").parse(directory_name)
+    }
+
+    pub fn parse_movie_directory(&self, directory_name: &str) -> ParsedRelease {
+        ReleaseParser::new("movie").parse(directory_name)
+    }
+
+    pub fn parse_season_directory(&self, directory_name: &str) -> Option<u32> {
+        let re = {
+            static RE: OnceLock<Regex> = OnceLock::new();
+            RE.get_or_init(|| Regex::new(r"(?i)season[. _]?(\d{1,2})|^S(\d{1,2})$").unwrap())
+        };
+        re.captures(directory_name)
+            .and_then(|caps| caps.get(1).or_else(|| caps.get(2)))
+            .and_then(|m| m.as_str().parse().ok())
+    }
+
+    pub fn to_library_path(&self, parsed: &ParsedRelease, extension: &str) -> String {
+        library_path::render(parsed, extension)
+    }
+
+    /// Like [`Self::parse`], but also reports which dot/space-delimited tokens didn't match any
+    /// known category, so callers can aggregate naming patterns the rule set is missing. Does not
+    /// change the `ParsedRelease` default `parse` produces.
+    pub fn parse_with_report(&self, release_name: &str) -> (ParsedRelease, ParseReport) {
+        let parsed = self.parse(release_name);
+        let report = self.build_report(release_name, &parsed);
+        (parsed, report)
+    }
+
+    /// Strips the same group tag `parse` strips before tokenizing, so the report isn't just
+    /// flagging the group tag on every release. An anime CRC32 tag is not stripped here; the report
+    /// recognizes it against `parsed.crc32` instead. Returns the trimmed text plus the byte offset
+    /// it starts at in `release_name`, so reported positions still point at the original string.
+    fn strip_known_wrapper<'a>(&self, without_extension: &'a str) -> (&'a str, usize) {
+        if self.release_type == "anime" {
+            let start = anime_group_re()
+                .captures(without_extension)
+                .map(|caps| caps.get(0).unwrap().as_str().len())
+                .unwrap_or(0);
+            (&without_extension[start..], start)
+        } else if group_re().is_match(without_extension) {
+            let end = group_re()
+                .find(without_extension)
+                .map(|m| m.start())
+                .unwrap_or(without_extension.len());
+            (&without_extension[..end], 0)
+        } else {
+            (without_extension, 0)
+        }
+    }
+
+    fn build_report(&self, release_name: &str, parsed: &ParsedRelease) -> ParseReport {
+        let without_extension = strip_extension(release_name);
+        let (remainder, offset) = self.strip_known_wrapper(&without_extension);
+        let title_words: HashSet<String> = parsed
+            .title
+            .split_whitespace()
+            .map(|w| w.to_ascii_lowercase())
+            .collect();
+
+        let mut unknown_tokens = Vec::new();
+        for (position, token) in tokenize_with_positions(remainder) {
+            if token.is_empty() {
+                continue;
+            }
+            let inner = token.trim_matches(|c: char| c == '[' || c == ']' || c == '(' || c == ')');
+            let lower = inner.to_ascii_lowercase();
+
+            let is_known = title_words.contains(&lower)
+                || catalog::is_known_metadata_token(inner)
+                || year_re().is_match(inner)
+                || season_episode_re().is_match(inner)
+                || (self.release_type == "anime"
+                    && (anime_episode_re().is_match(inner)
+                        || parsed
+                            .crc32
+                            .as_deref()
+                            .is_some_and(|crc32| crc32.eq_ignore_ascii_case(inner))));
+
+            if !is_known {
+                unknown_tokens.push(UnknownToken {
+                    token,
+                    position: position + offset,
+                });
+            }
+        }
+
+        ParseReport {
+            unknown_tokens,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn parse_path(&self, file_path: &str) -> Option<crate::types::PathInfo> {
+        let path = std::path::Path::new(file_path);
+        let file_name = path.file_name()?.to_str()?;
+        let file = self.parse(file_name);
+
+        let parent_name = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|s| s.to_str());
+
+        let season = parent_name.and_then(|name| self.parse_season_directory(name));
+
+        let directory = match parent_name {
+            Some(name) if season.is_none() => Some(self.parse(name)),
+            _ => None,
+        };
+
+        Some(crate::types::PathInfo {
+            directory,
+            season,
+            file,
+            full_path: file_path.to_string(),
+        })
+    }
+}
+
+fn strip_extension(name: &str) -> String {
+    match name.rfind('.') {
+        Some(idx) if name.len() - idx <= 5 && name[idx + 1..].chars().all(|c| c.is_ascii_alphanumeric()) => {
+            name[..idx].to_string()
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn clean_title(raw: &str) -> String {
+    raw.trim_matches(|c: char| c == '.' || c == '_' || c == ' ' || c == '-')
+        .replace(['.', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits `name` into dot/underscore/space-delimited tokens, each paired with its byte offset in
+/// `name`, for diagnostics that need to report where an unrecognized token came from.
+fn tokenize_with_positions(name: &str) -> Vec<(usize, String)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in name.char_indices() {
+        if c == '.' || c == '_' || c == ' ' {
+            if let Some(token_start) = start.take() {
+                tokens.push((token_start, name[token_start..i].to_string()));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(token_start) = start {
+        tokens.push((token_start, name[token_start..].to_string()));
+    }
+
+    tokens
+}
+
+/// `title`/`year` are already separator-normalized and stripped of resolution/source/group/flag
+/// noise by the parse above, so the lookup-ready query is just that title plus the year split
+/// out. `truncated_at_year` is set whenever a year was split out; the title then ends at or before
+/// that year, and the flag does not tell whether the year or an earlier catalog token ended it.
+fn build_search_query(parsed: &ParsedRelease) -> SearchQuery {
+    SearchQuery {
+        query: parsed.title.clone(),
+        year: parsed.year,
+        truncated_at_year: parsed.year.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_standard_movie_release() {
+        let parser = ReleaseParser::new("movie");
+        let parsed = parser.parse("The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv");
+
+        assert_eq!(parsed.title, "The Matrix");
+        assert_eq!(parsed.year, Some(1999));
+        assert_eq!(parsed.resolution.as_deref(), Some("1080p"));
+        assert_eq!(parsed.source.as_deref(), Some("BluRay"));
+        assert_eq!(parsed.format.as_deref(), Some("x264"));
+        assert_eq!(parsed.group.as_deref(), Some("GROUP"));
+    }
+
+    #[test]
+    fn builds_a_lookup_ready_search_query_for_a_series() {
+        let parser = ReleaseParser::new("series");
+        let parsed = parser.parse("The.Show.2019.1080p.WEB-DL.x264-GROUP.mkv");
+
+        assert_eq!(parsed.search_query.query, "The Show");
+        assert_eq!(parsed.search_query.year, Some(2019));
+        assert!(parsed.search_query.truncated_at_year);
+    }
+
+    #[test]
+    fn search_query_is_not_marked_truncated_without_a_year() {
+        let parser = ReleaseParser::new("movie");
+        let parsed = parser.parse("Untitled.Project.1080p.WEB-DL.x264-GROUP.mkv");
+
+        assert_eq!(parsed.search_query.query, "Untitled Project");
+        assert_eq!(parsed.search_query.year, None);
+        assert!(!parsed.search_query.truncated_at_year);
+    }
+
+    #[test]
+    fn search_query_strips_catalog_tokens_that_sit_before_the_year() {
+        let parser = ReleaseParser::new("movie");
+        let parsed = parser.parse("Movie.Name.EXTENDED.2020.1080p.BluRay.x264-GROUP.mkv");
+
+        assert_eq!(parsed.title, "Movie Name");
+        assert_eq!(parsed.search_query.query, "Movie Name");
+        assert_eq!(parsed.search_query.year, Some(2020));
+        assert!(parsed.search_query.truncated_at_year);
+    }
+
+    #[test]
+    fn parses_a_standard_series_release() {
+        let parser = ReleaseParser::new("series");
+        let parsed = parser.parse("Show.Name.S01E02.720p.WEB.x264-GROUP.mkv");
+
+        assert_eq!(parsed.title, "Show Name");
+        assert_eq!(parsed.season, Some(1));
+        assert_eq!(parsed.episode, Some(2));
+        assert_eq!(parsed.resolution.as_deref(), Some("720p"));
+        assert_eq!(parsed.group.as_deref(), Some("GROUP"));
+    }
+
+    #[test]
+    fn extracts_fansub_group_from_anime_release() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.group.as_deref(), Some("FansubGroup"));
+        assert_eq!(parsed.title, "Title");
+    }
+
+    #[test]
+    fn extracts_absolute_episode_from_anime_release() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.absolute_episode, Some(7));
+        assert_eq!(parsed.season, None);
+        assert_eq!(parsed.episode, None);
+    }
+
+    #[test]
+    fn extracts_crc32_from_anime_release() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.crc32.as_deref(), Some("A1B2C3D4"));
+    }
+
+    #[test]
+    fn extracts_source_and_resolution_from_anime_parens() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.source.as_deref(), Some("BD"));
+        assert_eq!(parsed.resolution.as_deref(), Some("1080p"));
+    }
+
+    #[test]
+    fn extracts_version_suffix_from_anime_episode() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07v2 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.absolute_episode, Some(7));
+        assert_eq!(parsed.version.as_deref(), Some("v2"));
+    }
+
+    #[test]
+    fn extracts_episode_range_from_anime_release() {
+        let parser = ReleaseParser::new("anime");
+        let parsed = parser.parse("[FansubGroup] Title - 07-09 (BD 1080p) [A1B2C3D4].mkv");
+
+        assert_eq!(parsed.absolute_episode, Some(7));
+        assert_eq!(parsed.episodes, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn parses_season_directory_names() {
+        let parser = ReleaseParser::new("tv");
+
+        assert_eq!(parser.parse_season_directory("Season 01"), Some(1));
+        assert_eq!(parser.parse_season_directory("Season.02"), Some(2));
+        assert_eq!(parser.parse_season_directory("S03"), Some(3));
+    }
+
+    #[test]
+    fn parses_a_path_into_directory_and_file() {
+        let parser = ReleaseParser::new("series");
+        let info = parser
+            .parse_path("/library/Show Name/Season 01/Show.Name.S01E02.720p.WEB.x264-GROUP.mkv")
+            .expect("path should parse");
+
+        assert_eq!(info.season, Some(1));
+        assert_eq!(info.file.episode, Some(2));
+    }
+
+    #[test]
+    fn renders_tv_library_path() {
+        let parser = ReleaseParser::new("series");
+        let parsed = parser.parse("Show.Name.S01E02.720p.WEB.x264-GROUP.mkv");
+
+        assert_eq!(
+            parser.to_library_path(&parsed, "mkv"),
+            "Show Name/Season 01/Show Name - S01E02.mkv"
+        );
+    }
+
+    #[test]
+    fn renders_multi_episode_library_path() {
+        let parser = ReleaseParser::new("series");
+        let parsed = parser.parse("Show.Name.S01E02-E03.720p.WEB.x264-GROUP.mkv");
+
+        assert_eq!(
+            parser.to_library_path(&parsed, "mkv"),
+            "Show Name/Season 01/Show Name - S01E02-E03.mkv"
+        );
+    }
+
+    #[test]
+    fn renders_tv_library_path_with_episode_title() {
+        let parser = ReleaseParser::new("series");
+        let mut parsed = parser.parse("Show.Name.S01E02.720p.WEB.x264-GROUP.mkv");
+        parsed.episode_title = Some("Who's There?".to_string());
+
+        assert_eq!(
+            parser.to_library_path(&parsed, "mkv"),
+            "Show Name/Season 01/Show Name - S01E02 - Who's There.mkv"
+        );
+    }
+
+    #[test]
+    fn renders_movie_library_path() {
+        let parser = ReleaseParser::new("movie");
+        let parsed = parser.parse("The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv");
+
+        assert_eq!(
+            parser.to_library_path(&parsed, "mkv"),
+            "The Matrix (1999)/The Matrix (1999) [1080p].mkv"
+        );
+    }
+
+    #[test]
+    fn sanitizes_filesystem_unsafe_characters_in_library_path() {
+        let parser = ReleaseParser::new("movie");
+        let mut parsed = parser.parse("Title.1999.1080p.BluRay.x264-GROUP.mkv");
+        parsed.title = "Title: The Return?".to_string();
+
+        assert_eq!(
+            parser.to_library_path(&parsed, "mkv"),
+            "Title The Return (1999)/Title The Return (1999) [1080p].mkv"
+        );
+    }
+
+    #[test]
+    fn release_parser_parses_correctly_when_shared_across_threads() {
+        let parser = ReleaseParser::new("movie");
+        let names = [
+            "The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv",
+            "Inception.2010.2160p.WEB-DL.x265-GROUP.mkv",
+            "Arrival.2016.720p.WEB.x264-GROUP.mkv",
+        ];
+
+        let results = std::thread::scope(|scope| {
+            names
+                .iter()
+                .map(|name| scope.spawn(|| parser.parse(name)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(results[0].title, "The Matrix");
+        assert_eq!(results[1].title, "Inception");
+        assert_eq!(results[2].title, "Arrival");
+    }
+
+    #[test]
+    fn parse_with_report_flags_an_unrecognized_token() {
+        let parser = ReleaseParser::new("movie");
+        let (parsed, report) = parser.parse_with_report("The.Matrix.1999.1080p.Wizzo.x264-GROUP.mkv");
+
+        assert_eq!(parsed.title, "The Matrix");
+        assert_eq!(report.unknown_tokens.len(), 1);
+        assert_eq!(report.unknown_tokens[0].token, "Wizzo");
+        assert_eq!(
+            report.unknown_tokens[0].position,
+            "The.Matrix.1999.1080p.".len()
+        );
+    }
+
+    #[test]
+    fn parse_with_report_has_no_unknown_tokens_for_a_fully_recognized_release() {
+        let parser = ReleaseParser::new("movie");
+        let (_, report) = parser.parse_with_report("The.Matrix.1999.1080p.BluRay.x264-GROUP.mkv");
+
+        assert!(report.unknown_tokens.is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+/// The structured result of parsing a single release name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedRelease {
+    pub release: String,
+    pub title: String,
+    pub episode_title: Option<String>,
+    pub group: Option<String>,
+    pub year: Option<u32>,
+    pub date: Option<String>,
+    pub season: Option<u32>,
+    pub episode: Option<u32>,
+    pub episodes: Vec<u32>,
+    pub disc: Option<u32>,
+    pub flags: Vec<String>,
+    pub source: Option<String>,
+    pub format: Option<String>,
+    pub resolution: Option<String>,
+    pub audio: Option<String>,
+    pub device: Option<String>,
+    pub os: Option<String>,
+    pub version: Option<String>,
+    pub language: BTreeMap<String, String>,
+    pub tmdb_id: Option<String>,
+    pub tvdb_id: Option<String>,
+    pub imdb_id: Option<String>,
+    pub edition: Option<String>,
+    pub hdr: Vec<String>,
+    pub streaming_provider: Option<String>,
+    pub release_type: String,
+    pub absolute_episode: Option<u32>,
+    pub crc32: Option<String>,
+    pub search_query: SearchQuery,
+}
+
+/// Directory/file relationship produced by [`crate::parser::ReleaseParser::parse_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathInfo {
+    pub directory: Option<ParsedRelease>,
+    pub season: Option<u32>,
+    pub file: ParsedRelease,
+    pub full_path: String,
+}
+
+/// A normalized, lookup-ready query for metadata providers (TMDB/TVDB/etc), derived from the
+/// already-cleaned `title`/`year` so callers don't have to strip scene noise themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub query: String,
+    pub year: Option<u32>,
+    pub truncated_at_year: bool,
+}
+
+/// A dot/space-delimited token that didn't match any known source/format/resolution/audio/flag/
+/// group/title category, along with its byte offset in the original release name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownToken {
+    pub token: String,
+    pub position: usize,
+}
+
+/// Diagnostics produced by [`crate::parser::ReleaseParser::parse_with_report`], for aggregating
+/// the naming patterns the rule set doesn't recognize yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    pub unknown_tokens: Vec<UnknownToken>,
+    pub warnings: Vec<String>,
+}
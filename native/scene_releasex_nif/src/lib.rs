@@ -1,7 +1,8 @@
+use rayon::prelude::*;
 use rustler::{Env, Error, Term};
 use rustler::Encoder;
 use scene_release::parser::ReleaseParser;
-use scene_release::types::PathInfo;
+use scene_release::types::{ParseReport, ParsedRelease, PathInfo, SearchQuery};
 
 rustler::init!("Elixir.SceneReleasex");
 
@@ -12,6 +13,30 @@ fn parse(env: Env<'_>, release_type: String, release_name: String) -> Result<Ter
     serialize_parsed_release(env, &parsed)
 }
 
+#[rustler::nif(name = "nif_parse_with_report")]
+fn parse_with_report(env: Env<'_>, release_type: String, release_name: String) -> Result<Term<'_>, Error> {
+    let parser = ReleaseParser::new(&release_type);
+    let (parsed, report) = parser.parse_with_report(&release_name);
+    serialize_parsed_release_with_report(env, &parsed, &report)
+}
+
+#[rustler::nif(name = "nif_parse_batch", schedule = "DirtyCpu")]
+fn parse_batch<'a>(env: Env<'a>, release_type: String, release_names: Vec<String>) -> Result<Term<'a>, Error> {
+    let parser = ReleaseParser::new(&release_type);
+
+    let parsed: Vec<ParsedRelease> = release_names
+        .par_iter()
+        .map(|release_name| parser.parse(release_name))
+        .collect();
+
+    let mut terms: Vec<Term<'a>> = Vec::with_capacity(parsed.len());
+    for release in &parsed {
+        terms.push(serialize_parsed_release(env, release)?);
+    }
+
+    Ok(terms.encode(env))
+}
+
 #[rustler::nif(name = "nif_parse_path")]
 fn parse_path(env: Env<'_>, release_type: String, file_path: String) -> Result<Term<'_>, Error> {
     let parser = ReleaseParser::new(&release_type);
@@ -23,6 +48,13 @@ fn parse_path(env: Env<'_>, release_type: String, file_path: String) -> Result<T
     }
 }
 
+#[rustler::nif(name = "nif_parse_anime")]
+fn parse_anime(env: Env<'_>, release_name: String) -> Result<Term<'_>, Error> {
+    let parser = ReleaseParser::new("anime");
+    let parsed = parser.parse(&release_name);
+    serialize_parsed_release(env, &parsed)
+}
+
 #[rustler::nif(name = "nif_parse_series_directory")]
 fn parse_series_directory(env: Env<'_>, directory_name: String) -> Result<Term<'_>, Error> {
     let parser = ReleaseParser::new("series");
@@ -37,6 +69,14 @@ fn parse_movie_directory(env: Env<'_>, directory_name: String) -> Result<Term<'_
     serialize_parsed_release(env, &parsed)
 }
 
+#[rustler::nif(name = "nif_library_path")]
+fn library_path(env: Env<'_>, release_type: String, release_name: String, extension: String) -> Result<Term<'_>, Error> {
+    let parser = ReleaseParser::new(&release_type);
+    let parsed = parser.parse(&release_name);
+    let path = parser.to_library_path(&parsed, &extension);
+    Ok(path.encode(env))
+}
+
 #[rustler::nif(name = "nif_parse_season_directory")]
 fn parse_season_directory(env: Env<'_>, directory_name: String) -> Result<Term<'_>, Error> {
     let parser = ReleaseParser::new("tv");
@@ -48,12 +88,12 @@ fn parse_season_directory(env: Env<'_>, directory_name: String) -> Result<Term<'
 }
 
 fn serialize_parsed_release<'a>(env: Env<'a>, parsed: &scene_release::types::ParsedRelease) -> Result<Term<'a>, Error> {
-    let mut pairs: Vec<(Term<'a>, Term<'a>)> = Vec::new();
-
-    pairs.push(("release".encode(env), parsed.release.encode(env)));
-    pairs.push(("title".encode(env), parsed.title.encode(env)));
-    pairs.push(("title_extra".encode(env), parsed.episode_title.encode(env)));
-    pairs.push(("group".encode(env), parsed.group.encode(env)));
+    let mut pairs: Vec<(Term<'a>, Term<'a>)> = vec![
+        ("release".encode(env), parsed.release.encode(env)),
+        ("title".encode(env), parsed.title.encode(env)),
+        ("title_extra".encode(env), parsed.episode_title.encode(env)),
+        ("group".encode(env), parsed.group.encode(env)),
+    ];
 
     match parsed.year {
         Some(year) => pairs.push(("year".encode(env), (year as i64).encode(env))),
@@ -125,9 +165,61 @@ fn serialize_parsed_release<'a>(env: Env<'a>, parsed: &scene_release::types::Par
     pairs.push(("streaming_provider".encode(env), parsed.streaming_provider.encode(env)));
     pairs.push(("type".encode(env), parsed.release_type.encode(env)));
 
+    match parsed.absolute_episode {
+        Some(absolute_episode) => pairs.push(("absolute_episode".encode(env), (absolute_episode as i64).encode(env))),
+        None => pairs.push(("absolute_episode".encode(env), rustler::types::atom::nil().encode(env))),
+    }
+
+    match &parsed.crc32 {
+        Some(crc32) => pairs.push(("crc32".encode(env), crc32.as_str().encode(env))),
+        None => pairs.push(("crc32".encode(env), rustler::types::atom::nil().encode(env))),
+    }
+
+    pairs.push(("search_query".encode(env), serialize_search_query(env, &parsed.search_query)?));
+
     Term::map_from_pairs(env, &pairs)
 }
 
+fn serialize_search_query<'a>(env: Env<'a>, search_query: &SearchQuery) -> Result<Term<'a>, Error> {
+    let mut pairs: Vec<(Term<'a>, Term<'a>)> = Vec::new();
+
+    pairs.push(("query".encode(env), search_query.query.as_str().encode(env)));
+
+    match search_query.year {
+        Some(year) => pairs.push(("year".encode(env), (year as i64).encode(env))),
+        None => pairs.push(("year".encode(env), rustler::types::atom::nil().encode(env))),
+    }
+
+    pairs.push(("truncated_at_year".encode(env), search_query.truncated_at_year.encode(env)));
+
+    Term::map_from_pairs(env, &pairs)
+}
+
+fn serialize_parsed_release_with_report<'a>(
+    env: Env<'a>,
+    parsed: &ParsedRelease,
+    report: &ParseReport,
+) -> Result<Term<'a>, Error> {
+    let base = serialize_parsed_release(env, parsed)?;
+
+    let unknown_tokens: Vec<Term<'a>> = report
+        .unknown_tokens
+        .iter()
+        .map(|unknown_token| {
+            let pairs: Vec<(Term<'a>, Term<'a>)> = vec![
+                ("token".encode(env), unknown_token.token.as_str().encode(env)),
+                ("position".encode(env), (unknown_token.position as i64).encode(env)),
+            ];
+            Term::map_from_pairs(env, &pairs)
+        })
+        .collect::<Result<Vec<Term<'a>>, Error>>()?;
+
+    let warnings: Vec<&str> = report.warnings.iter().map(|w| w.as_str()).collect();
+
+    let with_tokens = base.map_put("unknown_tokens".encode(env), unknown_tokens.encode(env))?;
+    with_tokens.map_put("warnings".encode(env), warnings.encode(env))
+}
+
 fn serialize_path_info<'a>(env: Env<'a>, path_info: &PathInfo) -> Result<Term<'a>, Error> {
     let mut pairs: Vec<(Term<'a>, Term<'a>)> = Vec::new();
 